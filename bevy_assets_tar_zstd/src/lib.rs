@@ -10,28 +10,125 @@
 //! ```
 //!
 //! This will read assets from the `assets` folder an write them into `./target/assets.bin`.
+//!
+//! On `wasm32` targets, `name.bin` is instead fetched over HTTP relative to the page origin
+//! (there's no filesystem or OS threads to fall back on there).
+//!
+//! Multiple archives can be mounted at once, each under its own prefix, so e.g. base game
+//! assets and a downloadable content pack can ship as separate `.bin` files and still be
+//! resolved through one `AssetServer`: load `"dlc://levels/bonus.scn"` to read `levels/bonus.scn`
+//! out of the archive mounted under the `dlc` prefix. See [`ArchiveMount`].
+//!
+//! On native targets, calling `AssetServer::watch_for_changes` rebuilds an archive's in-memory
+//! index when its `.bin` file is regenerated and fires an [`ArchiveReloaded`] event for it - see
+//! that event's docs for what it does and doesn't do for already-loaded asset handles.
 
+use aes_gcm::aead::{Aead, KeyInit};
+use aes_gcm::{Aes256Gcm, Key, Nonce};
 use bevy::asset::AssetIoError;
 use bevy::{asset::AssetIo, prelude::*};
+#[cfg(not(target_arch = "wasm32"))]
+use notify::Watcher;
+use std::collections::HashMap;
 use std::io::prelude::*;
 use std::path::PathBuf;
 use std::sync::{mpsc, Arc, Mutex};
 use std::thread;
 
+/// Controls how much of the archive is kept in memory after the initial index pass.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum IndexMode {
+    /// Cache every file's decompressed bytes in memory alongside the index, so later reads
+    /// are plain hash lookups. Uses the most RAM, but is the fastest to serve from.
+    Eager,
+    /// Only index paths and metadata (directory listings, file vs. directory). File contents
+    /// are decompressed on demand from a dedicated scan of the archive, so huge archives don't
+    /// have to sit fully in RAM.
+    Lazy,
+}
+
+impl Default for IndexMode {
+    fn default() -> Self {
+        IndexMode::Eager
+    }
+}
+
+/// One archive to mount, addressed under its own `prefix`. A path is routed to a mount by
+/// stripping a leading `"{prefix}://"`; paths with no `"://"` are routed to the mount with the
+/// empty-string prefix, so a single-archive config can be addressed with plain unprefixed paths.
 #[derive(Clone, Debug)]
-pub struct AssetsTarZstdConfig {
+pub struct ArchiveMount {
+    /// The prefix assets in this archive are addressed under, e.g. `"dlc"` for `dlc://foo.png`.
+    /// The empty string mounts the archive at the root, with no prefix required in asset paths.
+    pub prefix: String,
     /// The name of the asset directory (the resoulting .bin file without the extension)
     pub name: String,
+    /// Whether to cache file contents in memory (`Eager`) or decompress them on demand
+    /// (`Lazy`). See [`IndexMode`].
+    pub index_mode: IndexMode,
+    /// The AES-256-GCM key the archive was bundled with, if any. Must match the
+    /// `Config::encryption_key` used by `bevy_assets_tar_zstd_bundler` at build time, or the
+    /// archive will fail to authenticate.
+    pub encryption_key: Option<[u8; 32]>,
 }
 
-impl Default for AssetsTarZstdConfig {
+impl Default for ArchiveMount {
     fn default() -> Self {
         Self {
+            prefix: String::new(),
             name: "assets".into(),
+            index_mode: IndexMode::default(),
+            encryption_key: None,
+        }
+    }
+}
+
+#[derive(Clone, Debug)]
+pub struct AssetsTarZstdConfig {
+    /// The archives to mount. Must not be empty, and prefixes must be unique. Defaults to a
+    /// single archive named `assets`, mounted at the empty prefix.
+    pub archives: Vec<ArchiveMount>,
+}
+
+impl Default for AssetsTarZstdConfig {
+    fn default() -> Self {
+        Self {
+            archives: vec![ArchiveMount::default()],
         }
     }
 }
 
+/// Splits `"{prefix}://{rest}"` into `(prefix, rest)`. A path with no `"://"` is treated as
+/// unprefixed, i.e. routed to the mount registered under the empty-string prefix.
+fn split_mount_prefix(path: &str) -> (&str, &str) {
+    match path.split_once("://") {
+        Some((prefix, rest)) => (prefix, rest),
+        None => ("", path),
+    }
+}
+
+/// Enforces `AssetsTarZstdConfig::archives`'s documented invariants - non-empty, unique prefixes -
+/// before anything tries to mount them. Both are easy to violate by accident (an empty `Vec`, or
+/// two DLC packs both left at the default empty prefix), and either one would otherwise fail
+/// silently: an empty config loads nothing, and a prefix collision would quietly drop every mount
+/// but the last one sharing it once collected into the worker's `HashMap`.
+fn validate_archives(archives: &[ArchiveMount]) {
+    assert!(
+        !archives.is_empty(),
+        "AssetsTarZstdConfig::archives must not be empty"
+    );
+
+    let mut seen_prefixes = std::collections::HashSet::new();
+    for mount in archives {
+        assert!(
+            seen_prefixes.insert(mount.prefix.as_str()),
+            "AssetsTarZstdConfig::archives has more than one archive mounted at prefix {:?}",
+            mount.prefix
+        );
+    }
+}
+
+#[cfg(not(target_arch = "wasm32"))]
 #[derive(Debug)]
 enum Message {
     /// Read file (hopefully)
@@ -40,17 +137,155 @@ enum Message {
     RequestMetadata(String, mpsc::Sender<Option<bevy::asset::Metadata>>),
     /// Read files in a directory
     RequestDirFiles(String, mpsc::Sender<Option<Vec<PathBuf>>>),
+    /// The archive at this path changed on disk; rebuild its in-memory index, then send on the
+    /// given channel to acknowledge the rebuild finished. The watcher thread waits for that ack
+    /// before recording the reload, so an `ArchiveReloaded` event never fires ahead of the rebuild
+    /// it claims already happened.
+    Reload(PathBuf, mpsc::Sender<()>),
 }
 
+#[cfg(not(target_arch = "wasm32"))]
 struct AssetsTarZstd {
     tx: Arc<Mutex<mpsc::Sender<Message>>>,
     task: thread::JoinHandle<()>,
+    archive_paths: Vec<PathBuf>,
+    /// Set by `watch_for_changes`. Holds the `notify` watcher itself so later
+    /// `watch_path_for_changes` calls can register more paths with it; the events it produces
+    /// are consumed on a separate thread spawned alongside it (see `watch_for_changes`).
+    filesystem_watcher: Arc<Mutex<Option<notify::RecommendedWatcher>>>,
+    /// Archive paths that changed since `AssetsTarZstdPlugin` last drained this into
+    /// [`ArchiveReloaded`] events - see that type's docs for the full rationale.
+    reloaded: Arc<Mutex<Vec<PathBuf>>>,
 }
 
-fn find<'b, 'c, 'd>(
-    archive: &'b mut tar::Archive<zstd::Decoder<'c, std::io::BufReader<std::fs::File>>>,
-    path: &str,
-) -> Option<tar::Entry<'b, zstd::Decoder<'c, std::io::BufReader<std::fs::File>>>> {
+/// A single mounted, already-indexed archive, as held by the worker thread in `spawn_async`.
+#[cfg(not(target_arch = "wasm32"))]
+struct MountedArchive {
+    archive_path: PathBuf,
+    encryption_key: Option<[u8; 32]>,
+    index_mode: IndexMode,
+    index: ArchiveIndex,
+}
+
+const DECRYPTION_CHUNK_NONCE_COUNTER_OFFSET: usize = 8;
+
+/// Upper bound on a single chunk's ciphertext length: `bevy_assets_tar_zstd_bundler`'s
+/// `EncryptingWriter` never seals more than its 64 KiB plaintext chunk size plus the 16 byte
+/// GCM tag. The 4 byte length prefix read in `fill_buffer` arrives before authentication can
+/// run, so without this cap a corrupted or tampered prefix could claim up to ~4 GB and get
+/// allocated before the tag check ever rejects it.
+const MAX_ENCRYPTED_CHUNK_LEN: usize = 64 * 1024 + 16;
+
+/// Reads the chunked AES-256-GCM stream written by `bevy_assets_tar_zstd_bundler`'s
+/// `EncryptingWriter`, undoing it one chunk at a time: a 12 byte base nonce header, then
+/// `(4 byte little-endian ciphertext length, ciphertext || tag)` chunks, each authenticated and
+/// decrypted in turn. Implements `Read` directly (rather than exposing itself as `BufRead`) so
+/// it can sit behind a plain `std::io::BufReader` just like the unencrypted path.
+struct DecryptingReader<R: Read> {
+    inner: R,
+    cipher: Aes256Gcm,
+    base_nonce: [u8; 12],
+    chunk_index: u32,
+    buffer: Vec<u8>,
+    pos: usize,
+    finished: bool,
+}
+
+impl<R: Read> DecryptingReader<R> {
+    fn new(mut inner: R, key: &[u8; 32]) -> std::io::Result<Self> {
+        let mut base_nonce = [0u8; 12];
+        inner.read_exact(&mut base_nonce)?;
+
+        Ok(Self {
+            inner,
+            cipher: Aes256Gcm::new(Key::<Aes256Gcm>::from_slice(key)),
+            base_nonce,
+            chunk_index: 0,
+            buffer: Vec::new(),
+            pos: 0,
+            finished: false,
+        })
+    }
+
+    fn chunk_nonce(&self) -> Nonce<Aes256Gcm> {
+        let mut nonce = self.base_nonce;
+        for (byte, counter_byte) in nonce[DECRYPTION_CHUNK_NONCE_COUNTER_OFFSET..]
+            .iter_mut()
+            .zip(self.chunk_index.to_be_bytes())
+        {
+            *byte ^= counter_byte;
+        }
+        *Nonce::<Aes256Gcm>::from_slice(&nonce)
+    }
+
+    fn fill_buffer(&mut self) -> std::io::Result<bool> {
+        let mut len_buf = [0u8; 4];
+        match self.inner.read_exact(&mut len_buf) {
+            Ok(()) => {}
+            Err(err) if err.kind() == std::io::ErrorKind::UnexpectedEof => {
+                self.finished = true;
+                return Ok(false);
+            }
+            Err(err) => return Err(err),
+        }
+
+        let len = u32::from_le_bytes(len_buf) as usize;
+        if len > MAX_ENCRYPTED_CHUNK_LEN {
+            return Err(std::io::Error::new(
+                std::io::ErrorKind::InvalidData,
+                "encrypted chunk length exceeds maximum, archive is corrupt or was tampered with",
+            ));
+        }
+
+        let mut ciphertext = vec![0u8; len];
+        self.inner.read_exact(&mut ciphertext)?;
+
+        let nonce = self.chunk_nonce();
+        self.buffer = self.cipher.decrypt(&nonce, ciphertext.as_ref()).map_err(|_| {
+            std::io::Error::new(
+                std::io::ErrorKind::InvalidData,
+                "AES-GCM authentication failed, archive is corrupt or was tampered with",
+            )
+        })?;
+        self.pos = 0;
+        self.chunk_index += 1;
+        Ok(true)
+    }
+}
+
+impl<R: Read> Read for DecryptingReader<R> {
+    fn read(&mut self, buf: &mut [u8]) -> std::io::Result<usize> {
+        if self.pos >= self.buffer.len() && !self.finished && !self.fill_buffer()? {
+            return Ok(0);
+        }
+
+        let n = std::cmp::min(buf.len(), self.buffer.len() - self.pos);
+        buf[..n].copy_from_slice(&self.buffer[self.pos..self.pos + n]);
+        self.pos += n;
+        Ok(n)
+    }
+}
+
+/// The reader `open_archive` decompresses from: either the archive file directly, or a
+/// [`DecryptingReader`] wrapping it when `ArchiveMount::encryption_key` is set.
+/// `zstd::Decoder::new` buffers whichever variant it gets, so `Source` only needs `Read`.
+#[cfg(not(target_arch = "wasm32"))]
+enum Source {
+    Plain(std::fs::File),
+    Encrypted(DecryptingReader<std::fs::File>),
+}
+
+#[cfg(not(target_arch = "wasm32"))]
+impl Read for Source {
+    fn read(&mut self, buf: &mut [u8]) -> std::io::Result<usize> {
+        match self {
+            Source::Plain(r) => r.read(buf),
+            Source::Encrypted(r) => r.read(buf),
+        }
+    }
+}
+
+fn find<'b, R: Read>(archive: &'b mut tar::Archive<R>, path: &str) -> Option<tar::Entry<'b, R>> {
     archive
         .entries()
         .ok()?
@@ -59,85 +294,178 @@ fn find<'b, 'c, 'd>(
         .find(|e| *e.path().unwrap().to_string_lossy() == *path)
 }
 
+/// Everything we know about the archive without touching the file again: file/directory
+/// metadata for every entry, the direct children of every directory, and (in [`IndexMode::Eager`])
+/// the decompressed bytes of every regular file. Built once (in [`spawn_async`] natively, or
+/// after the initial fetch on wasm32) so that later reads are hash lookups instead of a fresh
+/// decompression pass over the whole archive. [`build_index`] is generic over the archive's
+/// underlying reader so the same indexing code serves the native (file-backed) and wasm32 (in-memory) backends.
+struct ArchiveIndex {
+    contents: Option<HashMap<PathBuf, Vec<u8>>>,
+    metadata: HashMap<PathBuf, bevy::asset::Metadata>,
+    dir_children: HashMap<PathBuf, Vec<PathBuf>>,
+}
+
+fn build_index<R: Read>(archive: &mut tar::Archive<R>, index_mode: IndexMode) -> ArchiveIndex {
+    let mut metadata = HashMap::new();
+    let mut dir_children: HashMap<PathBuf, Vec<PathBuf>> = HashMap::new();
+    let mut contents = match index_mode {
+        IndexMode::Eager => Some(HashMap::new()),
+        IndexMode::Lazy => None,
+    };
+
+    for entry in archive
+        .entries()
+        .expect("Expected valid tar stream")
+        .filter_map(|e| e.ok())
+    {
+        let mut entry = entry;
+        let entry_path: PathBuf = match entry.path() {
+            Ok(path) => path.into_owned(),
+            Err(_) => continue,
+        };
+
+        let file_type = match entry.header().entry_type() {
+            tar::EntryType::Regular => bevy::asset::FileType::File,
+            tar::EntryType::Directory => bevy::asset::FileType::Directory,
+            _ => continue,
+        };
+
+        if let Some(parent) = entry_path.parent() {
+            dir_children
+                .entry(parent.to_path_buf())
+                .or_default()
+                .push(entry_path.clone());
+        }
+
+        if let (Some(contents), bevy::asset::FileType::File) = (contents.as_mut(), file_type) {
+            let mut buffer = Vec::new();
+            entry
+                .read_to_end(&mut buffer)
+                .expect("Expected to read archive entry");
+            contents.insert(entry_path.clone(), buffer);
+        }
+
+        metadata.insert(entry_path, bevy::asset::Metadata::new(file_type));
+    }
+
+    for children in dir_children.values_mut() {
+        children.sort();
+    }
+
+    ArchiveIndex {
+        contents,
+        metadata,
+        dir_children,
+    }
+}
+
+#[cfg(not(target_arch = "wasm32"))]
 fn read_bytes(
-    archive: &mut tar::Archive<zstd::Decoder<std::io::BufReader<std::fs::File>>>,
+    index: &ArchiveIndex,
+    archive_path: &PathBuf,
+    encryption_key: Option<&[u8; 32]>,
     path: &str,
 ) -> Option<Vec<u8>> {
-    let mut entry = find(archive, path)?;
+    let key = PathBuf::from(path);
+
+    if let Some(contents) = &index.contents {
+        return contents.get(&key).cloned();
+    }
 
+    // Lazy mode: nothing but metadata was cached, so decompress a dedicated scan to fetch
+    // this one file's bytes.
+    let mut archive = open_archive(archive_path, encryption_key);
+    let mut entry = find(&mut archive, path)?;
     let mut buffer = Vec::new();
     entry.read_to_end(&mut buffer).ok()?;
-
     Some(buffer)
 }
 
-fn read_metadata(
-    archive: &mut tar::Archive<zstd::Decoder<std::io::BufReader<std::fs::File>>>,
-    path: &str,
-) -> Option<bevy::asset::Metadata> {
-    let entry = find(archive, path)?;
+fn read_metadata(index: &ArchiveIndex, path: &str) -> Option<bevy::asset::Metadata> {
+    index.metadata.get(&PathBuf::from(path)).cloned()
+}
+
+fn read_dir_files(index: &ArchiveIndex, path: &str) -> Option<Vec<PathBuf>> {
+    index.dir_children.get(&PathBuf::from(path)).cloned()
+}
 
-    let file_type = match entry.header().entry_type() {
-        tar::EntryType::Regular => bevy::asset::FileType::File,
-        tar::EntryType::Directory => bevy::asset::FileType::Directory,
-        _ => return None,
+#[cfg(not(target_arch = "wasm32"))]
+fn open_archive(
+    path: &PathBuf,
+    encryption_key: Option<&[u8; 32]>,
+) -> tar::Archive<zstd::Decoder<'static, std::io::BufReader<Source>>> {
+    let file_reader = std::fs::File::open(path.clone())
+        .expect(format!("Expected {} file", path.to_string_lossy().to_string()).as_str());
+
+    let source = match encryption_key {
+        Some(key) => Source::Encrypted(
+            DecryptingReader::new(file_reader, key).expect("Expected valid encrypted archive"),
+        ),
+        None => Source::Plain(file_reader),
     };
 
-    Some(bevy::asset::Metadata::new(file_type))
+    let decoder = zstd::Decoder::new(source).expect("Expected valid zstd encoding");
+    tar::Archive::new(decoder)
 }
 
-fn read_dir_files(
-    archive: &mut tar::Archive<zstd::Decoder<std::io::BufReader<std::fs::File>>>,
-    path: &str,
-) -> Option<Vec<PathBuf>> {
-    let mut result: Vec<PathBuf> = archive
-        .entries()
-        .ok()?
-        .into_iter()
-        .filter_map(|e| e.ok())
-        .filter(|e| e.path().is_ok())
-        .filter(|e| e.path().unwrap().parent().is_some())
-        .filter(|e| *e.path().unwrap().parent().unwrap().to_string_lossy() == *path)
-        .map(|e| e.path().unwrap().into())
-        .collect();
+/// Resolves `name.bin` next to, or in the parent directory of, the current executable - the
+/// same search `spawn_async` used to do inline, pulled out so `AssetsTarZstd::new` can resolve
+/// it up front too (to know what to hand `notify` once hot-reloading is enabled).
+#[cfg(not(target_arch = "wasm32"))]
+fn resolve_archive_path(name: &str) -> PathBuf {
+    let paths = [
+        std::env::current_exe()
+            .unwrap()
+            .join(format!("{}.bin", name)),
+        std::env::current_exe()
+            .unwrap()
+            .parent()
+            .expect("Expected parent path relative to executable")
+            .join(format!("{}.bin", name)),
+    ];
 
-    result.sort();
-    Some(result)
+    paths
+        .into_iter()
+        .find(|p| p.is_file())
+        .expect("Expected assets.bin file")
 }
 
+#[cfg(not(target_arch = "wasm32"))]
 fn spawn_async(
     config: AssetsTarZstdConfig,
 ) -> (Arc<Mutex<mpsc::Sender<Message>>>, thread::JoinHandle<()>) {
     let (tx, rx) = mpsc::channel();
     let task = thread::spawn(move || {
-        // Search the assets.bin file
-        let paths = [
-            std::env::current_exe()
-                .unwrap()
-                .join(format!("{}.bin", config.name)),
-            std::env::current_exe()
-                .unwrap()
-                .parent()
-                .expect("Expected parent path relative to executable")
-                .join(format!("{}.bin", config.name)),
-        ];
-
-        let archive_path = paths
+        // Build every mount's index once up front, keyed by prefix, so requests never have to
+        // reopen and re-decompress a whole archive again.
+        let mut mounts: HashMap<String, MountedArchive> = config
+            .archives
             .into_iter()
-            .find(|p| p.is_file())
-            .expect("Expected assets.bin file");
-
-        info!("Assets archive path: {}", archive_path.to_string_lossy());
-
-        // Open archive
-        fn open_archive(
-            path: &PathBuf,
-        ) -> tar::Archive<zstd::Decoder<'static, std::io::BufReader<std::fs::File>>> {
-            let file_reader = std::fs::File::open(path.clone())
-                .expect(format!("Expected {} file", path.to_string_lossy().to_string()).as_str());
-            let decoder = zstd::Decoder::new(file_reader).expect("Expected valid zstd encoding");
-            return tar::Archive::new(decoder);
-        }
+            .map(|mount| {
+                let archive_path = resolve_archive_path(&mount.name);
+                info!(
+                    "Mounting {} archive at path {}",
+                    if mount.prefix.is_empty() {
+                        "root".to_string()
+                    } else {
+                        format!("{:?}", mount.prefix)
+                    },
+                    archive_path.to_string_lossy()
+                );
+                let mut archive = open_archive(&archive_path, mount.encryption_key.as_ref());
+                let index = build_index(&mut archive, mount.index_mode);
+                (
+                    mount.prefix,
+                    MountedArchive {
+                        archive_path,
+                        encryption_key: mount.encryption_key,
+                        index_mode: mount.index_mode,
+                        index,
+                    },
+                )
+            })
+            .collect();
 
         info!("Started asset loader");
 
@@ -145,26 +473,55 @@ fn spawn_async(
             match msg {
                 Message::RequestFile(path, result) => {
                     debug!("Requested file {}", path);
-                    let mut archive = open_archive(&archive_path);
-
+                    let (prefix, rest) = split_mount_prefix(&path);
                     result
-                        .send(read_bytes(&mut archive, path.as_str()))
+                        .send(mounts.get(prefix).and_then(|mount| {
+                            read_bytes(
+                                &mount.index,
+                                &mount.archive_path,
+                                mount.encryption_key.as_ref(),
+                                rest,
+                            )
+                        }))
                         .unwrap_or_else(|err| error!("{}", err));
                 }
                 Message::RequestMetadata(path, result) => {
                     debug!("Requested metadata of file {}", path);
-                    let mut archive = open_archive(&archive_path);
+                    let (prefix, rest) = split_mount_prefix(&path);
                     result
-                        .send(read_metadata(&mut archive, path.as_str()))
+                        .send(
+                            mounts
+                                .get(prefix)
+                                .and_then(|mount| read_metadata(&mount.index, rest)),
+                        )
                         .unwrap_or_else(|err| error!("{}", err));
                 }
                 Message::RequestDirFiles(path, result) => {
                     debug!("Requested files in directory {}", path);
-                    let mut archive = open_archive(&archive_path);
+                    let (prefix, rest) = split_mount_prefix(&path);
                     result
-                        .send(read_dir_files(&mut archive, path.as_str()))
+                        .send(
+                            mounts
+                                .get(prefix)
+                                .and_then(|mount| read_dir_files(&mount.index, rest)),
+                        )
                         .unwrap_or_else(|err| error!("{}", err));
                 }
+                Message::Reload(changed_path, done) => {
+                    if let Some((_, mount)) = mounts
+                        .iter_mut()
+                        .find(|(_, mount)| mount.archive_path == changed_path)
+                    {
+                        info!(
+                            "Archive {} changed, rebuilding index",
+                            changed_path.to_string_lossy()
+                        );
+                        let mut archive =
+                            open_archive(&mount.archive_path, mount.encryption_key.as_ref());
+                        mount.index = build_index(&mut archive, mount.index_mode);
+                    }
+                    done.send(()).unwrap_or_else(|err| error!("{}", err));
+                }
             }
         }
     });
@@ -172,13 +529,28 @@ fn spawn_async(
     (Arc::new(Mutex::new(tx)), task)
 }
 
+#[cfg(not(target_arch = "wasm32"))]
 impl AssetsTarZstd {
     fn new(config: AssetsTarZstdConfig) -> Self {
+        validate_archives(&config.archives);
+
+        let archive_paths = config
+            .archives
+            .iter()
+            .map(|mount| resolve_archive_path(&mount.name))
+            .collect();
         let (tx, task) = spawn_async(config);
-        Self { tx, task }
+        Self {
+            tx,
+            task,
+            archive_paths,
+            filesystem_watcher: Arc::new(Mutex::new(None)),
+            reloaded: Arc::new(Mutex::new(Vec::new())),
+        }
     }
 }
 
+#[cfg(not(target_arch = "wasm32"))]
 impl AssetIo for AssetsTarZstd {
     fn is_dir(&self, path: &std::path::Path) -> bool {
         if let Ok(result) = self.get_metadata(path) {
@@ -267,19 +639,321 @@ impl AssetIo for AssetsTarZstd {
     }
 
     fn watch_for_changes(&self) -> anyhow::Result<(), bevy::asset::AssetIoError> {
+        let mut guard = self.filesystem_watcher.lock().unwrap();
+        if guard.is_some() {
+            return Ok(());
+        }
+
+        let (sender, receiver) = mpsc::channel();
+        let watcher = notify::watcher(sender, std::time::Duration::from_millis(200)).map_err(
+            |_| AssetIoError::NotFound(self.archive_paths[0].clone()),
+        )?;
+
+        // Rebuilding the index makes every later read see the new archive; `AssetsTarZstdPlugin`
+        // drains `reloaded` into `ArchiveReloaded` events every frame so a consumer's own system
+        // can act on it (see that event's docs for why that's needed at all).
+        let archive_paths = self.archive_paths.clone();
+        let tx = self.tx.lock().unwrap().clone();
+        let reloaded = self.reloaded.clone();
+        thread::spawn(move || {
+            for event in receiver.iter() {
+                let changed_path = match event {
+                    notify::DebouncedEvent::Write(path) => path,
+                    notify::DebouncedEvent::Create(path) => path,
+                    notify::DebouncedEvent::Rename(_, new_path) => new_path,
+                    _ => continue,
+                };
+                // We watch each archive's parent directory rather than the archive file itself
+                // (see `watch_path_for_changes`), so this also sees writes to unrelated files in
+                // that directory - filter down to paths that are actually one of our archives.
+                if !archive_paths.contains(&changed_path) {
+                    continue;
+                }
+
+                let (done_tx, done_rx) = mpsc::channel();
+                if tx.send(Message::Reload(changed_path.clone(), done_tx)).is_err() {
+                    break;
+                }
+                // Wait for the actor thread to finish rebuilding the index before recording the
+                // reload, so `ArchiveReloaded` never fires for a rebuild that hasn't happened yet.
+                done_rx.recv().ok();
+                reloaded.lock().unwrap().push(changed_path);
+            }
+        });
+
+        *guard = Some(watcher);
         Ok(())
     }
 
     fn watch_path_for_changes(
         &self,
-        path: &std::path::Path,
+        _path: &std::path::Path,
     ) -> anyhow::Result<(), bevy::asset::AssetIoError> {
+        // Every asset comes out of one of the mounted archives, so there's nothing per-asset-path
+        // specific to watch - just make sure every archive file is registered with the watcher
+        // `watch_for_changes` created.
+        //
+        // We watch each archive's parent directory rather than the archive file itself: bundling
+        // regenerates `name.bin` via `remove_file` + `File::create` (a fresh inode at the same
+        // path), and a watch on the file itself dies with the removed inode and never sees the
+        // replacement. A directory watch survives that; `watch_for_changes` filters its events
+        // back down to our archive paths.
+        let mut guard = self.filesystem_watcher.lock().unwrap();
+        if let Some(watcher) = guard.as_mut() {
+            let mut watched_dirs = std::collections::HashSet::new();
+            for archive_path in &self.archive_paths {
+                let dir = archive_path
+                    .parent()
+                    .ok_or_else(|| AssetIoError::NotFound(archive_path.clone()))?;
+                if watched_dirs.insert(dir.to_path_buf()) {
+                    watcher
+                        .watch(dir, notify::RecursiveMode::NonRecursive)
+                        .map_err(|_| AssetIoError::NotFound(archive_path.clone()))?;
+                }
+            }
+        }
         Ok(())
     }
 }
 
+/// wasm32 has neither a filesystem to resolve `name.bin` against nor OS threads to run a
+/// background worker on, so the archive is fetched over HTTP instead and the resulting
+/// [`ArchiveIndex`] lives behind an `Rc<RefCell<..>>` on the (single) main thread rather than
+/// being message-passed to a dedicated thread like the native backend does.
+#[cfg(target_arch = "wasm32")]
+mod wasm {
+    use super::{
+        build_index, split_mount_prefix, validate_archives, ArchiveIndex, AssetsTarZstdConfig,
+        DecryptingReader, HashMap, PathBuf,
+    };
+    use bevy::asset::AssetIoError;
+    use bevy::{asset::AssetIo, prelude::*};
+    use std::cell::RefCell;
+    use std::future::Future;
+    use std::io::{Cursor, Read};
+    use std::pin::Pin;
+    use std::rc::Rc;
+    use std::task::{Context, Poll, Waker};
+    use wasm_bindgen::{JsCast, JsValue};
+    use wasm_bindgen_futures::JsFuture;
+
+    enum Source {
+        Plain(Cursor<Vec<u8>>),
+        Encrypted(DecryptingReader<Cursor<Vec<u8>>>),
+    }
+
+    impl Read for Source {
+        fn read(&mut self, buf: &mut [u8]) -> std::io::Result<usize> {
+            match self {
+                Source::Plain(r) => r.read(buf),
+                Source::Encrypted(r) => r.read(buf),
+            }
+        }
+    }
+
+    /// Fetches and indexes every configured mount, keyed by its prefix, so `AssetsTarZstd` can
+    /// route a path to the right archive the same way the native backend's `spawn_async` does.
+    async fn fetch_and_index_all(
+        config: &AssetsTarZstdConfig,
+    ) -> Result<HashMap<String, ArchiveIndex>, JsValue> {
+        let mut indices = HashMap::new();
+        for mount in &config.archives {
+            let index = fetch_archive(mount).await?;
+            indices.insert(mount.prefix.clone(), index);
+        }
+        Ok(indices)
+    }
+
+    /// Fetches `{name}.bin` relative to the page origin and decompresses (and, if configured,
+    /// decrypts) it entirely in memory, building the same [`ArchiveIndex`] the native backend
+    /// builds from a file. There's no benefit to `IndexMode::Lazy` here (the whole archive is
+    /// already in memory as the raw fetch response), so it's treated the same as `Eager`.
+    async fn fetch_archive(mount: &super::ArchiveMount) -> Result<ArchiveIndex, JsValue> {
+        let window = web_sys::window().expect("Expected to run inside a browser window");
+        let url = format!("{}.bin", mount.name);
+        let response: web_sys::Response =
+            JsFuture::from(window.fetch_with_str(&url)).await?.dyn_into()?;
+        let array_buffer = JsFuture::from(response.array_buffer()?).await?;
+        let bytes = js_sys::Uint8Array::new(&array_buffer).to_vec();
+
+        let source = match &mount.encryption_key {
+            Some(key) => Source::Encrypted(
+                DecryptingReader::new(Cursor::new(bytes), key)
+                    .map_err(|err| JsValue::from_str(&err.to_string()))?,
+            ),
+            None => Source::Plain(Cursor::new(bytes)),
+        };
+        let decoder =
+            zstd::Decoder::new(source).map_err(|err| JsValue::from_str(&err.to_string()))?;
+        let mut archive = tar::Archive::new(decoder);
+        Ok(build_index(&mut archive, super::IndexMode::Eager))
+    }
+
+    pub(crate) struct AssetsTarZstd {
+        indices: Rc<RefCell<Option<HashMap<String, ArchiveIndex>>>>,
+        /// Wakers for every `IndicesReady` currently parked on `indices` being filled in, woken
+        /// all at once by the `spawn_local` task in `new`. More than one asset load can race
+        /// ahead of the fetch finishing, so this has to hold every waiter, not just the latest.
+        wakers: Rc<RefCell<Vec<Waker>>>,
+    }
+
+    impl AssetsTarZstd {
+        pub(crate) fn new(config: AssetsTarZstdConfig) -> Self {
+            validate_archives(&config.archives);
+
+            let indices = Rc::new(RefCell::new(None));
+            let wakers = Rc::new(RefCell::new(Vec::new()));
+            let indices_handle = indices.clone();
+            let wakers_handle = wakers.clone();
+            wasm_bindgen_futures::spawn_local(async move {
+                match fetch_and_index_all(&config).await {
+                    Ok(built) => *indices_handle.borrow_mut() = Some(built),
+                    Err(err) => error!("Failed to fetch/index archive mounts: {:?}", err),
+                }
+                for waker in wakers_handle.borrow_mut().drain(..) {
+                    waker.wake();
+                }
+            });
+            Self { indices, wakers }
+        }
+
+        /// Awaits the archive fetch/index started in `new`, so the first `load_path` call is
+        /// always correct even if it races ahead of it. Synchronous `AssetIo` methods can't await
+        /// this and instead just report `NotFound` until the index is ready.
+        async fn ready_indices(&self) -> std::cell::Ref<HashMap<String, ArchiveIndex>> {
+            IndicesReady {
+                indices: &self.indices,
+                wakers: &self.wakers,
+            }
+            .await
+        }
+    }
+
+    /// A `Future` polling whether `new`'s fetch/index task has finished, parking its waker so
+    /// that task can be woken instead of spin-polling. An already-resolved `Promise` can't be
+    /// used for this: awaiting one schedules a microtask, and a loop that keeps scheduling new
+    /// microtasks before yielding never lets the browser dequeue the macrotask that would
+    /// actually deliver the fetch response, hanging forever.
+    struct IndicesReady<'a> {
+        indices: &'a Rc<RefCell<Option<HashMap<String, ArchiveIndex>>>>,
+        wakers: &'a Rc<RefCell<Vec<Waker>>>,
+    }
+
+    impl<'a> Future for IndicesReady<'a> {
+        type Output = std::cell::Ref<'a, HashMap<String, ArchiveIndex>>;
+
+        fn poll(self: Pin<&mut Self>, cx: &mut Context<'_>) -> Poll<Self::Output> {
+            if self.indices.borrow().is_some() {
+                Poll::Ready(std::cell::Ref::map(self.indices.borrow(), |i| {
+                    i.as_ref().unwrap()
+                }))
+            } else {
+                self.wakers.borrow_mut().push(cx.waker().clone());
+                Poll::Pending
+            }
+        }
+    }
+
+    impl AssetIo for AssetsTarZstd {
+        fn is_dir(&self, path: &std::path::Path) -> bool {
+            if let Ok(result) = self.get_metadata(path) {
+                result.is_dir()
+            } else {
+                false
+            }
+        }
+
+        fn is_file(&self, path: &std::path::Path) -> bool {
+            if let Ok(result) = self.get_metadata(path) {
+                result.is_file()
+            } else {
+                false
+            }
+        }
+
+        fn load_path<'a>(
+            &'a self,
+            path: &'a std::path::Path,
+        ) -> bevy::utils::BoxedFuture<'a, anyhow::Result<Vec<u8>, bevy::asset::AssetIoError>> {
+            let path = path.to_string_lossy().to_string();
+            Box::pin(async move {
+                let indices = self.ready_indices().await;
+                let (prefix, rest) = split_mount_prefix(&path);
+                let key = PathBuf::from(rest);
+                indices
+                    .get(prefix)
+                    .and_then(|index| index.contents.as_ref())
+                    .and_then(|contents| contents.get(&key))
+                    .cloned()
+                    .ok_or(AssetIoError::NotFound(key))
+            })
+        }
+
+        fn get_metadata(
+            &self,
+            path: &std::path::Path,
+        ) -> anyhow::Result<bevy::asset::Metadata, bevy::asset::AssetIoError> {
+            let path = path.to_string_lossy().to_string();
+            let (prefix, rest) = split_mount_prefix(&path);
+            let key = PathBuf::from(rest);
+            self.indices
+                .borrow()
+                .as_ref()
+                .and_then(|indices| indices.get(prefix))
+                .and_then(|index| index.metadata.get(&key))
+                .cloned()
+                .ok_or(AssetIoError::NotFound(key))
+        }
+
+        fn read_directory(
+            &self,
+            path: &std::path::Path,
+        ) -> anyhow::Result<Box<dyn Iterator<Item = std::path::PathBuf>>, bevy::asset::AssetIoError>
+        {
+            let path = path.to_string_lossy().to_string();
+            let (prefix, rest) = split_mount_prefix(&path);
+            let key = PathBuf::from(rest);
+            self.indices
+                .borrow()
+                .as_ref()
+                .and_then(|indices| indices.get(prefix))
+                .and_then(|index| index.dir_children.get(&key))
+                .cloned()
+                .map(|children| Box::new(children.into_iter()) as Box<dyn Iterator<Item = _>>)
+                .ok_or(AssetIoError::NotFound(key))
+        }
+
+        fn watch_for_changes(&self) -> anyhow::Result<(), bevy::asset::AssetIoError> {
+            Ok(())
+        }
+
+        fn watch_path_for_changes(
+            &self,
+            _path: &std::path::Path,
+        ) -> anyhow::Result<(), bevy::asset::AssetIoError> {
+            Ok(())
+        }
+    }
+}
+
+#[cfg(target_arch = "wasm32")]
+use wasm::AssetsTarZstd;
+
 //    bevy::asset::create_platform_default_asset_io
 
+/// Fired (native targets only) whenever a watched archive is regenerated on disk and its
+/// in-memory index has been rebuilt.
+///
+/// Bevy's built-in hot-reload machinery only knows how to refresh already-loaded asset handles
+/// for its own `FileAssetIo`, and isn't reachable from a custom `AssetIo` like this one - so
+/// rebuilding the index is as far as this crate can take a reload on its own. Listen for this
+/// event in your own system and re-request whichever assets you need fresh.
+#[derive(Clone, Debug)]
+pub struct ArchiveReloaded {
+    /// The path of the archive file that changed.
+    pub path: PathBuf,
+}
+
 #[derive(Default)]
 pub struct AssetsTarZstdPlugin {
     config: AssetsTarZstdConfig,
@@ -300,6 +974,304 @@ impl Plugin for AssetsTarZstdPlugin {
         }
 
         let io = AssetsTarZstd::new(self.config.clone());
+
+        #[cfg(not(target_arch = "wasm32"))]
+        {
+            let reloaded = io.reloaded.clone();
+            app.add_event::<ArchiveReloaded>();
+            app.add_system(move |mut events: EventWriter<ArchiveReloaded>| {
+                for path in reloaded.lock().unwrap().drain(..) {
+                    events.send(ArchiveReloaded { path });
+                }
+            });
+        }
+
         app.insert_resource(AssetServer::new(io));
     }
 }
+
+#[cfg(all(test, not(target_arch = "wasm32")))]
+mod tests {
+    use super::*;
+
+    /// Builds a correctly-framed encrypted stream by hand (the same format
+    /// `bevy_assets_tar_zstd_bundler`'s `EncryptingWriter` writes) and checks `DecryptingReader`
+    /// undoes it, across several chunk boundaries, back to the original bytes.
+    #[test]
+    fn decrypting_reader_round_trips_across_chunk_boundaries() {
+        let key = [3u8; 32];
+        let plaintext: Vec<u8> = (0..20_000).map(|i| (i % 199) as u8).collect();
+        let chunk_size = 4096;
+
+        let encrypted = encrypt_for_test(&key, &plaintext, chunk_size);
+
+        let mut reader = DecryptingReader::new(std::io::Cursor::new(encrypted), &key).unwrap();
+        let mut decrypted = Vec::new();
+        reader.read_to_end(&mut decrypted).unwrap();
+
+        assert_eq!(decrypted, plaintext);
+    }
+
+    #[test]
+    fn decrypting_reader_rejects_tampered_ciphertext() {
+        let key = [3u8; 32];
+        let plaintext = b"some asset bytes that should not be tamperable".to_vec();
+        let mut encrypted = encrypt_for_test(&key, &plaintext, 4096);
+
+        // Flip a bit inside the first chunk's ciphertext (right after the 12 byte base nonce
+        // and 4 byte length prefix), leaving the authentication tag itself untouched.
+        encrypted[16] ^= 0x01;
+
+        let mut reader = DecryptingReader::new(std::io::Cursor::new(encrypted), &key).unwrap();
+        let mut decrypted = Vec::new();
+        let err = reader.read_to_end(&mut decrypted).unwrap_err();
+        assert_eq!(err.kind(), std::io::ErrorKind::InvalidData);
+    }
+
+    /// Hand-rolled equivalent of `bevy_assets_tar_zstd_bundler`'s `EncryptingWriter`, kept here
+    /// so this crate's tests don't need a dependency on the bundler crate just to build fixtures.
+    fn encrypt_for_test(key: &[u8; 32], plaintext: &[u8], chunk_size: usize) -> Vec<u8> {
+        let cipher = Aes256Gcm::new(Key::<Aes256Gcm>::from_slice(key));
+        let base_nonce = [9u8; 12];
+
+        let mut out = Vec::new();
+        out.extend_from_slice(&base_nonce);
+
+        for (chunk_index, chunk) in plaintext.chunks(chunk_size).enumerate() {
+            let mut nonce = base_nonce;
+            for (byte, counter_byte) in nonce[DECRYPTION_CHUNK_NONCE_COUNTER_OFFSET..]
+                .iter_mut()
+                .zip((chunk_index as u32).to_be_bytes())
+            {
+                *byte ^= counter_byte;
+            }
+            let ciphertext = cipher
+                .encrypt(Nonce::<Aes256Gcm>::from_slice(&nonce), chunk)
+                .unwrap();
+            out.extend_from_slice(&(ciphertext.len() as u32).to_le_bytes());
+            out.extend_from_slice(&ciphertext);
+        }
+
+        out
+    }
+
+    /// Builds an in-memory tar archive (not compressed) with a couple of nested directories and
+    /// files, for exercising `build_index`/`read_bytes` against the real tar-parsing logic rather
+    /// than a hand-constructed `ArchiveIndex`.
+    fn build_test_archive() -> Vec<u8> {
+        fn append_dir(builder: &mut tar::Builder<Vec<u8>>, path: &str) {
+            let mut header = tar::Header::new_gnu();
+            header.set_path(path).unwrap();
+            header.set_entry_type(tar::EntryType::Directory);
+            header.set_size(0);
+            header.set_mode(0o755);
+            header.set_cksum();
+            builder.append(&header, std::io::empty()).unwrap();
+        }
+
+        fn append_file(builder: &mut tar::Builder<Vec<u8>>, path: &str, contents: &[u8]) {
+            let mut header = tar::Header::new_gnu();
+            header.set_path(path).unwrap();
+            header.set_entry_type(tar::EntryType::Regular);
+            header.set_size(contents.len() as u64);
+            header.set_mode(0o644);
+            header.set_cksum();
+            builder.append(&header, contents).unwrap();
+        }
+
+        let mut builder = tar::Builder::new(Vec::new());
+        builder.mode(tar::HeaderMode::Deterministic);
+
+        append_dir(&mut builder, "a");
+        append_dir(&mut builder, "a/b");
+        append_file(&mut builder, "root.txt", b"root contents");
+        append_file(&mut builder, "a/file1.txt", b"file1 contents");
+        append_file(&mut builder, "a/b/file2.txt", b"file2 contents");
+
+        builder.into_inner().unwrap()
+    }
+
+    #[test]
+    fn build_index_eager_indexes_nested_files_and_dirs() {
+        let mut archive = tar::Archive::new(std::io::Cursor::new(build_test_archive()));
+        let index = build_index(&mut archive, IndexMode::Eager);
+
+        assert_eq!(
+            index.metadata.get(&PathBuf::from("root.txt")).map(|m| m.is_file()),
+            Some(true)
+        );
+        assert_eq!(
+            index.metadata.get(&PathBuf::from("a")).map(|m| m.is_dir()),
+            Some(true)
+        );
+        assert_eq!(
+            index.metadata.get(&PathBuf::from("a/b")).map(|m| m.is_dir()),
+            Some(true)
+        );
+
+        let mut root_children = index.dir_children[&PathBuf::from("")].clone();
+        root_children.sort();
+        assert_eq!(
+            root_children,
+            vec![PathBuf::from("a"), PathBuf::from("root.txt")]
+        );
+
+        let mut a_children = index.dir_children[&PathBuf::from("a")].clone();
+        a_children.sort();
+        assert_eq!(
+            a_children,
+            vec![PathBuf::from("a/b"), PathBuf::from("a/file1.txt")]
+        );
+
+        let contents = index.contents.as_ref().unwrap();
+        assert_eq!(
+            contents.get(&PathBuf::from("root.txt")),
+            Some(&b"root contents".to_vec())
+        );
+        assert_eq!(
+            contents.get(&PathBuf::from("a/b/file2.txt")),
+            Some(&b"file2 contents".to_vec())
+        );
+    }
+
+    #[test]
+    fn build_index_lazy_keeps_metadata_but_skips_contents() {
+        let mut archive = tar::Archive::new(std::io::Cursor::new(build_test_archive()));
+        let index = build_index(&mut archive, IndexMode::Lazy);
+
+        assert!(index.contents.is_none());
+        assert_eq!(
+            index
+                .metadata
+                .get(&PathBuf::from("a/file1.txt"))
+                .map(|m| m.is_file()),
+            Some(true)
+        );
+    }
+
+    #[test]
+    fn read_metadata_and_read_dir_files_look_up_the_built_index() {
+        let mut archive = tar::Archive::new(std::io::Cursor::new(build_test_archive()));
+        let index = build_index(&mut archive, IndexMode::Eager);
+
+        assert_eq!(
+            read_metadata(&index, "a/file1.txt").map(|m| m.is_file()),
+            Some(true)
+        );
+        assert_eq!(read_metadata(&index, "missing.txt"), None);
+
+        let mut children = read_dir_files(&index, "a").unwrap();
+        children.sort();
+        assert_eq!(
+            children,
+            vec![PathBuf::from("a/b"), PathBuf::from("a/file1.txt")]
+        );
+    }
+
+    /// `read_bytes` in `IndexMode::Lazy` has nothing cached and has to reopen and rescan the
+    /// archive file on disk to pull out one entry's bytes - exercise that path against a real
+    /// zstd-compressed tar file rather than the in-memory fixture the other tests use.
+    #[test]
+    fn read_bytes_lazy_rescans_archive_on_disk() {
+        let tar_bytes = build_test_archive();
+        let mut encoder = zstd::Encoder::new(Vec::new(), 0).unwrap();
+        encoder.write_all(&tar_bytes).unwrap();
+        let compressed = encoder.finish().unwrap();
+
+        let archive_path = std::env::temp_dir().join(format!(
+            "bevy_assets_tar_zstd_test_{}_{}.bin",
+            std::process::id(),
+            "read_bytes_lazy_rescans_archive_on_disk"
+        ));
+        std::fs::write(&archive_path, &compressed).unwrap();
+
+        let mut archive_for_index = tar::Archive::new(std::io::Cursor::new(tar_bytes));
+        let index = build_index(&mut archive_for_index, IndexMode::Lazy);
+
+        let bytes = read_bytes(&index, &archive_path, None, "a/b/file2.txt");
+        std::fs::remove_file(&archive_path).ok();
+
+        assert_eq!(bytes, Some(b"file2 contents".to_vec()));
+    }
+
+    fn mount_with(metadata: &[(&str, bevy::asset::FileType)], children: &[(&str, &[&str])]) -> ArchiveIndex {
+        let mut index_metadata = HashMap::new();
+        for (path, file_type) in metadata {
+            index_metadata.insert(PathBuf::from(path), bevy::asset::Metadata::new(*file_type));
+        }
+
+        let mut dir_children = HashMap::new();
+        for (dir, files) in children {
+            dir_children.insert(
+                PathBuf::from(dir),
+                files.iter().map(PathBuf::from).collect(),
+            );
+        }
+
+        ArchiveIndex {
+            contents: Some(HashMap::new()),
+            metadata: index_metadata,
+            dir_children,
+        }
+    }
+
+    /// Builds two mounts ("" and "dlc") the way `spawn_async` does, and checks that requests are
+    /// routed to the right one based on the path's `"{prefix}://"` prefix.
+    #[test]
+    fn multi_mount_routes_requests_by_prefix() {
+        assert_eq!(split_mount_prefix("foo.png"), ("", "foo.png"));
+        assert_eq!(
+            split_mount_prefix("dlc://levels/bonus.scn"),
+            ("dlc", "levels/bonus.scn")
+        );
+
+        let mut base_index = mount_with(
+            &[("foo.png", bevy::asset::FileType::File)],
+            &[(".", &["foo.png"])],
+        );
+        base_index
+            .contents
+            .as_mut()
+            .unwrap()
+            .insert(PathBuf::from("foo.png"), b"base pixels".to_vec());
+
+        let mut dlc_index = mount_with(
+            &[("levels/bonus.scn", bevy::asset::FileType::File)],
+            &[("levels", &["levels/bonus.scn"])],
+        );
+        dlc_index
+            .contents
+            .as_mut()
+            .unwrap()
+            .insert(PathBuf::from("levels/bonus.scn"), b"bonus level".to_vec());
+
+        let mut mounts = HashMap::new();
+        mounts.insert("".to_string(), base_index);
+        mounts.insert("dlc".to_string(), dlc_index);
+
+        let dummy_path = PathBuf::from("unused.bin");
+
+        let (prefix, rest) = split_mount_prefix("foo.png");
+        assert_eq!(
+            read_bytes(&mounts[prefix], &dummy_path, None, rest),
+            Some(b"base pixels".to_vec())
+        );
+
+        let (prefix, rest) = split_mount_prefix("dlc://levels/bonus.scn");
+        assert_eq!(
+            read_bytes(&mounts[prefix], &dummy_path, None, rest),
+            Some(b"bonus level".to_vec())
+        );
+        assert_eq!(
+            read_dir_files(&mounts[prefix], "levels"),
+            Some(vec![PathBuf::from("levels/bonus.scn")])
+        );
+
+        // "foo.png" only exists under the root mount, not under "dlc".
+        let (prefix, rest) = split_mount_prefix("dlc://foo.png");
+        assert_eq!(read_bytes(&mounts[prefix], &dummy_path, None, rest), None);
+
+        // An unknown prefix has no mount to route to at all.
+        assert!(mounts.get("unknown").is_none());
+    }
+}